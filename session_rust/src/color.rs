@@ -1,4 +1,7 @@
+use crate::schema::{self, Versioned};
+use crate::JsonFormat;
 use serde::{ser::Serialize as SerTrait, Deserialize, Serialize};
+use serde_json::Value;
 use std::fmt;
 use uuid::Uuid;
 
@@ -27,7 +30,10 @@ use uuid::Uuid;
 /// println!("Red: {}", red);
 /// ```
 #[derive(Debug, Clone, Serialize, Deserialize)]
+// `tag` only affects serialization: loading goes through `try_from`, where
+// the object form is checked by `ColorObject`.
 #[serde(tag = "type", rename = "Color")]
+#[serde(try_from = "Value")]
 pub struct Color {
     pub guid: Uuid,
     pub name: String,
@@ -37,6 +43,39 @@ pub struct Color {
     pub a: u8,
 }
 
+/// The tagged object form written by `to_json_data`.
+#[derive(Deserialize)]
+#[serde(tag = "type", rename = "Color")]
+struct ColorObject {
+    guid: Uuid,
+    name: String,
+    r: u8,
+    g: u8,
+    b: u8,
+    a: u8,
+}
+
+impl TryFrom<Value> for Color {
+    type Error = String;
+
+    fn try_from(data: Value) -> Result<Self, Self::Error> {
+        match data {
+            Value::Array(values) => Self::from_json_array(&values),
+            data => {
+                let c: ColorObject = serde_json::from_value(data).map_err(|e| e.to_string())?;
+                Ok(Color {
+                    guid: c.guid,
+                    name: c.name,
+                    r: c.r,
+                    g: c.g,
+                    b: c.b,
+                    a: c.a,
+                })
+            }
+        }
+    }
+}
+
 impl Color {
     /// Create new color.
     pub fn new(r: u8, g: u8, b: u8, a: u8) -> Self {
@@ -84,23 +123,227 @@ impl Color {
         )
     }
 
+    /// Invert RGB components, keeping alpha.
+    ///
+    /// ```rust
+    /// use session_rust::Color;
+    ///
+    /// let inverted = Color::white().invert();
+    /// assert_eq!((inverted.r, inverted.g, inverted.b, inverted.a), (0, 0, 0, 255));
+    /// ```
+    pub fn invert(&self) -> Self {
+        Color::new(255 - self.r, 255 - self.g, 255 - self.b, self.a)
+    }
+
+    /// Convert to grayscale using luminance weights (0.299, 0.587, 0.114), keeping alpha.
+    ///
+    /// ```rust
+    /// use session_rust::Color;
+    ///
+    /// let gray = Color::new(255, 0, 0, 200).to_grayscale();
+    /// assert_eq!((gray.r, gray.g, gray.b, gray.a), (76, 76, 76, 200));
+    /// ```
+    pub fn to_grayscale(&self) -> Self {
+        let luminance = 0.299 * self.r as f32 + 0.587 * self.g as f32 + 0.114 * self.b as f32;
+        let l = luminance.round().min(255.0) as u8;
+        Color::new(l, l, l, self.a)
+    }
+
+    /// Multiply RGB components by alpha/255, keeping alpha.
+    ///
+    /// ```rust
+    /// use session_rust::Color;
+    ///
+    /// let color = Color::new(200, 100, 50, 128).premultiply();
+    /// assert_eq!((color.r, color.g, color.b, color.a), (100, 50, 25, 128));
+    /// ```
+    pub fn premultiply(&self) -> Self {
+        let alpha = self.a as f32 / 255.0;
+        Color::new(
+            (self.r as f32 * alpha).round() as u8,
+            (self.g as f32 * alpha).round() as u8,
+            (self.b as f32 * alpha).round() as u8,
+            self.a,
+        )
+    }
+
+    /// Convert RGB to CIE L*a*b* (sRGB, D65 white point), ignoring alpha.
+    fn to_lab(&self) -> [f32; 3] {
+        fn linear(c: u8) -> f32 {
+            let c = c as f32 / 255.0;
+            if c <= 0.04045 {
+                c / 12.92
+            } else {
+                ((c + 0.055) / 1.055).powf(2.4)
+            }
+        }
+        fn f(t: f32) -> f32 {
+            if t > 216.0 / 24389.0 {
+                t.cbrt()
+            } else {
+                (24389.0 / 27.0 * t + 16.0) / 116.0
+            }
+        }
+        let (r, g, b) = (linear(self.r), linear(self.g), linear(self.b));
+        let x = (0.4124 * r + 0.3576 * g + 0.1805 * b) / 0.95047;
+        let y = 0.2126 * r + 0.7152 * g + 0.0722 * b;
+        let z = (0.0193 * r + 0.1192 * g + 0.9505 * b) / 1.08883;
+        let (fx, fy, fz) = (f(x), f(y), f(z));
+        [116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz)]
+    }
+
+    /// Perceptual color difference (CIE76 Delta E in Lab space), ignoring alpha.
+    ///
+    /// ```rust
+    /// use session_rust::Color;
+    ///
+    /// let red = Color::new(255, 0, 0, 255);
+    /// assert_eq!(red.delta_e(&red), 0.0);
+    /// assert!(red.delta_e(&Color::new(0, 0, 255, 255)) > 100.0);
+    /// ```
+    pub fn delta_e(&self, other: &Color) -> f32 {
+        let a = self.to_lab();
+        let b = other.to_lab();
+        ((a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2) + (a[2] - b[2]).powi(2)).sqrt()
+    }
+
+    /// Index of the palette color with the smallest Delta E, or None for an empty palette.
+    ///
+    /// ```rust
+    /// use session_rust::Color;
+    ///
+    /// let palette = [
+    ///     Color::new(255, 0, 0, 255),
+    ///     Color::new(0, 255, 0, 255),
+    ///     Color::new(0, 0, 255, 255),
+    /// ];
+    /// assert_eq!(Color::new(240, 20, 10, 255).nearest_in(&palette), Some(0));
+    /// assert_eq!(Color::white().nearest_in(&[]), None);
+    /// ```
+    pub fn nearest_in(&self, palette: &[Color]) -> Option<usize> {
+        palette
+            .iter()
+            .map(|color| self.delta_e(color))
+            .enumerate()
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(index, _)| index)
+    }
+
+    /// Create from a `[r, g, b, a]` JSON array.
+    ///
+    /// Components are normalized floats when all of them lie in `[0, 1]`,
+    /// otherwise integers in 0-255. Negative, non-finite or out-of-range
+    /// values are rejected, and so are fractional values in 0-255 mode.
+    fn from_json_array(values: &[Value]) -> Result<Self, String> {
+        if values.len() != 4 {
+            return Err(format!(
+                "expected a color array of 4 components, found {}",
+                values.len()
+            ));
+        }
+        let mut components = [0.0f64; 4];
+        for (component, value) in components.iter_mut().zip(values) {
+            *component = value
+                .as_f64()
+                .filter(|v| v.is_finite() && *v >= 0.0)
+                .ok_or_else(|| format!("invalid color component: {}", value))?;
+        }
+        let normalized = components.iter().all(|v| *v <= 1.0);
+        let mut rgba = [0u8; 4];
+        for (channel, value) in rgba.iter_mut().zip(components) {
+            let scaled = if normalized {
+                (value * 255.0).round()
+            } else if value <= 255.0 && value.fract() == 0.0 {
+                value
+            } else {
+                return Err(format!(
+                    "color component must be an integer in 0-255: {}",
+                    value
+                ));
+            };
+            *channel = scaled as u8;
+        }
+        Ok(Color::new(rgba[0], rgba[1], rgba[2], rgba[3]))
+    }
+
     /// Serialize to JSON string (for cross-language compatibility)
     pub fn to_json_data(&self) -> Result<String, Box<dyn std::error::Error>> {
         let mut buf = Vec::new();
         let formatter = serde_json::ser::PrettyFormatter::with_indent(b"    ");
         let mut ser = serde_json::Serializer::with_formatter(&mut buf, formatter);
-        SerTrait::serialize(self, &mut ser)?;
+        SerTrait::serialize(&Versioned::new(self), &mut ser)?;
         Ok(String::from_utf8(buf)?)
     }
 
     /// Deserialize from JSON string (for cross-language compatibility)
+    ///
+    /// When the data has no `"version"` field, a missing `guid` gets a new
+    /// UUID, a missing `name` becomes `"Color"` and a missing `a` becomes 255.
+    ///
+    /// Besides the object form, a bare `[r, g, b, a]` array is accepted. Its
+    /// components are read as 0-1 floats when all of them lie in `[0, 1]`,
+    /// otherwise as 0-255 integers. Negative, non-finite and out-of-range
+    /// components are errors, as are fractional components in 0-255 mode
+    /// (e.g. `127.6`). Serialization always writes the object form.
+    ///
+    /// ```rust
+    /// use session_rust::Color;
+    ///
+    /// let color = Color::from_json_data(r#"{"type":"Color","r":255,"g":0,"b":0}"#).unwrap();
+    /// assert_eq!((color.r, color.g, color.b, color.a), (255, 0, 0, 255));
+    /// assert_eq!(color.name, "Color");
+    ///
+    /// let color = Color::from_json_data("[255, 128, 0, 255]").unwrap();
+    /// assert_eq!((color.r, color.g, color.b, color.a), (255, 128, 0, 255));
+    ///
+    /// let color = Color::from_json_data("[1.0, 0.502, 0.0, 1.0]").unwrap();
+    /// assert_eq!((color.r, color.g, color.b, color.a), (255, 128, 0, 255));
+    ///
+    /// assert!(Color::from_json_data("[300, 0, 0, 255]").is_err());
+    /// assert!(Color::from_json_data("[-0.4, 0, 0, 255]").is_err());
+    /// assert!(Color::from_json_data("[127.6, 0, 0, 255]").is_err());
+    ///
+    /// let err = Color::from_json_data("[255, 0, 0]").unwrap_err();
+    /// assert!(err.to_string().contains("4 components, found 3"));
+    ///
+    /// let json = r#"{"type":"Color","guid":"00000000-0000-0000-0000-000000000000",
+    ///     "name":"red","r":255,"g":0,"b":0,"version":1}"#;
+    /// let err = Color::from_json_data(json).unwrap_err();
+    /// assert!(err.to_string().contains("missing field `a`"));
+    /// ```
     pub fn from_json_data(json_data: &str) -> Result<Self, Box<dyn std::error::Error>> {
-        Ok(serde_json::from_str(json_data)?)
+        let mut data: Value = serde_json::from_str(json_data)?;
+        let version = schema::read_version(&data)?;
+        Self::migrate_json(&mut data, version);
+        Ok(serde_json::from_value(data)?)
+    }
+
+    /// Upgrade JSON data written with an older schema version in place.
+    pub(crate) fn migrate_json(data: &mut Value, version: u64) {
+        if version == 0 {
+            if let Some(map) = data.as_object_mut() {
+                schema::insert_default(map, "guid", Value::from(Uuid::new_v4().to_string()));
+                schema::insert_default(map, "name", Value::from("Color"));
+                schema::insert_default(map, "a", Value::from(255));
+            }
+        }
     }
 
     /// Serialize to JSON file
     pub fn to_json(&self, filepath: &str) -> Result<(), Box<dyn std::error::Error>> {
-        let json = self.to_json_data()?;
+        self.to_json_with_format(filepath, JsonFormat::Pretty)
+    }
+
+    /// Serialize to JSON file with compact or pretty formatting
+    pub fn to_json_with_format(
+        &self,
+        filepath: &str,
+        format: JsonFormat,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let json = match format {
+            JsonFormat::Compact => serde_json::to_string(&Versioned::new(self))?,
+            JsonFormat::Pretty => self.to_json_data()?,
+        };
         std::fs::write(filepath, json)?;
         Ok(())
     }
@@ -0,0 +1,22 @@
+/// Output formatting used when writing objects to JSON files.
+///
+/// `Pretty` produces the indented output returned by `to_json_data` and is
+/// convenient for debugging and diffing. `Compact` omits all whitespace,
+/// which keeps files small for large datasets.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use session_rust::{JsonFormat, Point};
+///
+/// let point = Point::new(1.0, 2.0, 3.0);
+/// point.to_json_with_format("point.json", JsonFormat::Compact).unwrap();
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum JsonFormat {
+    /// Single-line JSON without whitespace.
+    Compact,
+    /// Indented JSON (4 spaces), matching `to_json_data`.
+    #[default]
+    Pretty,
+}
@@ -30,6 +30,15 @@ pub mod point;
 /// Color module containing the Color struct and its implementations.
 pub mod color;
 
+/// JSON output formatting options shared by the file writers.
+pub mod json_format;
+
+/// JSON schema versioning and migration of older files.
+pub mod schema;
+
+/// Prelude module re-exporting the common types for glob import.
+pub mod prelude;
+
 /// A 3D point with visual properties.
 ///
 /// Re-exported from the point module for convenience.
@@ -39,3 +48,13 @@ pub use point::Point;
 ///
 /// Re-exported from the color module for convenience.
 pub use color::Color;
+
+/// JSON output formatting selector.
+///
+/// Re-exported from the json_format module for convenience.
+pub use json_format::JsonFormat;
+
+/// Current JSON schema version.
+///
+/// Re-exported from the schema module for convenience.
+pub use schema::SCHEMA_VERSION;
@@ -1,6 +1,9 @@
-use crate::Color;
+use crate::schema::{self, Versioned};
+use crate::{Color, JsonFormat};
 use serde::{ser::Serialize as SerTrait, Deserialize, Serialize};
+use serde_json::Value;
 use std::fmt;
+use std::ops::{Index, IndexMut};
 use uuid::Uuid;
 
 /// A 3D point with visual properties and cross-language JSON serialization support.
@@ -79,6 +82,7 @@ impl Point {
     ///
     /// This method creates a formatted JSON representation of the point
     /// for cross-language compatibility with Python and C++ implementations.
+    /// The output ends with a `"version"` field holding `SCHEMA_VERSION`.
     ///
     /// # Returns
     ///
@@ -88,17 +92,17 @@ impl Point {
     /// # Examples
     ///
     /// ```rust
-    /// use session_rust::Point;
+    /// use session_rust::{Point, SCHEMA_VERSION};
     ///
     /// let point = Point::new(1.0, 2.0, 3.0);
     /// let json = point.to_json_data().unwrap();
-    /// println!("JSON: {}", json);
+    /// assert!(json.contains(&format!("\"version\": {}", SCHEMA_VERSION)));
     /// ```
     pub fn to_json_data(&self) -> Result<String, Box<dyn std::error::Error>> {
         let mut buf = Vec::new();
         let formatter = serde_json::ser::PrettyFormatter::with_indent(b"    ");
         let mut ser = serde_json::Serializer::with_formatter(&mut buf, formatter);
-        SerTrait::serialize(self, &mut ser)?;
+        SerTrait::serialize(&Versioned::new(self), &mut ser)?;
         Ok(String::from_utf8(buf)?)
     }
 
@@ -107,6 +111,10 @@ impl Point {
     /// Creates a Point instance from JSON data, enabling cross-language
     /// data exchange with Python and C++ implementations.
     ///
+    /// Data without a `"version"` field is treated as version 0 and migrated:
+    /// missing `guid`, `name`, `width` and `pointcolor` get the same defaults
+    /// as `Point::new`. From version 1 on these fields are required.
+    ///
     /// # Arguments
     ///
     /// * `json_data` - JSON string representation of the Point
@@ -118,15 +126,42 @@ impl Point {
     ///
     /// # Examples
     ///
-    /// ```rust,no_run
+    /// ```rust
     /// use session_rust::Point;
     ///
     /// let json = r#"{"type":"Point","x":1.0,"y":2.0,"z":3.0}"#;
     /// let point = Point::from_json_data(json).unwrap();
     /// assert_eq!(point.x, 1.0);
+    /// assert_eq!(point.width, 1.0);
+    /// assert_eq!(point.name, "my_point");
+    ///
+    /// let json = r#"{"type":"Point","x":1.0,"y":2.0,"z":3.0,"version":1}"#;
+    /// assert!(Point::from_json_data(json).is_err());
     /// ```
     pub fn from_json_data(json_data: &str) -> Result<Self, Box<dyn std::error::Error>> {
-        Ok(serde_json::from_str(json_data)?)
+        let mut data: Value = serde_json::from_str(json_data)?;
+        let version = schema::read_version(&data)?;
+        Self::migrate_json(&mut data, version);
+        Ok(serde_json::from_value(data)?)
+    }
+
+    /// Upgrades JSON data written with an older schema version in place.
+    fn migrate_json(data: &mut Value, version: u64) {
+        if version == 0 {
+            if let Some(map) = data.as_object_mut() {
+                schema::insert_default(map, "guid", Value::from(Uuid::new_v4().to_string()));
+                schema::insert_default(map, "name", Value::from("my_point"));
+                schema::insert_default(map, "width", Value::from(1.0));
+                match map.get_mut("pointcolor") {
+                    Some(color) => Color::migrate_json(color, version),
+                    None => {
+                        if let Ok(color) = serde_json::to_value(Color::white()) {
+                            map.insert("pointcolor".to_string(), color);
+                        }
+                    }
+                }
+            }
+        }
     }
 
     /// Serializes the Point to a JSON file.
@@ -152,7 +187,54 @@ impl Point {
     /// point.to_json("point.json").unwrap();
     /// ```
     pub fn to_json(&self, filepath: &str) -> Result<(), Box<dyn std::error::Error>> {
-        let json = self.to_json_data()?;
+        self.to_json_with_format(filepath, JsonFormat::Pretty)
+    }
+
+    /// Serializes the Point to a JSON file using the given formatting.
+    ///
+    /// Use `JsonFormat::Compact` to keep files small and `JsonFormat::Pretty`
+    /// for human-readable output. Both forms load back with `from_json`.
+    ///
+    /// # Arguments
+    ///
+    /// * `filepath` - Path to the output JSON file
+    /// * `format` - Compact or pretty output
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the file was written successfully
+    /// * `Err(Box<dyn std::error::Error>)` - If writing fails
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use session_rust::{JsonFormat, Point};
+    ///
+    /// let dir = std::env::temp_dir();
+    /// let compact = dir.join("session_point_compact.json");
+    /// let pretty = dir.join("session_point_pretty.json");
+    ///
+    /// let point = Point::new(1.0, 2.0, 3.0);
+    /// point.to_json_with_format(compact.to_str().unwrap(), JsonFormat::Compact).unwrap();
+    /// point.to_json_with_format(pretty.to_str().unwrap(), JsonFormat::Pretty).unwrap();
+    ///
+    /// let compact_len = std::fs::metadata(&compact).unwrap().len();
+    /// let pretty_len = std::fs::metadata(&pretty).unwrap().len();
+    /// assert!(compact_len < pretty_len);
+    ///
+    /// let a = Point::from_json(compact.to_str().unwrap()).unwrap();
+    /// let b = Point::from_json(pretty.to_str().unwrap()).unwrap();
+    /// assert_eq!((a.guid, a.x, a.y, a.z), (b.guid, b.x, b.y, b.z));
+    /// ```
+    pub fn to_json_with_format(
+        &self,
+        filepath: &str,
+        format: JsonFormat,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let json = match format {
+            JsonFormat::Compact => serde_json::to_string(&Versioned::new(self))?,
+            JsonFormat::Pretty => self.to_json_data()?,
+        };
         std::fs::write(filepath, json)?;
         Ok(())
     }
@@ -200,3 +282,59 @@ impl fmt::Display for Point {
         )
     }
 }
+
+impl Index<usize> for Point {
+    type Output = f32;
+
+    /// Returns the coordinate at `index` (0 = x, 1 = y, 2 = z).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is greater than 2.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use session_rust::Point;
+    ///
+    /// let point = Point::new(1.0, 2.0, 3.0);
+    /// assert_eq!(point[0], 1.0);
+    /// assert_eq!(point[1], 2.0);
+    /// assert_eq!(point[2], 3.0);
+    /// ```
+    fn index(&self, index: usize) -> &f32 {
+        match index {
+            0 => &self.x,
+            1 => &self.y,
+            2 => &self.z,
+            _ => panic!("Point index out of range: {}", index),
+        }
+    }
+}
+
+impl IndexMut<usize> for Point {
+    /// Returns a mutable reference to the coordinate at `index` (0 = x, 1 = y, 2 = z).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is greater than 2.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use session_rust::Point;
+    ///
+    /// let mut point = Point::new(1.0, 2.0, 3.0);
+    /// point[2] = 5.0;
+    /// assert_eq!(point.z, 5.0);
+    /// assert_eq!(point.name, "my_point");
+    /// ```
+    fn index_mut(&mut self, index: usize) -> &mut f32 {
+        match index {
+            0 => &mut self.x,
+            1 => &mut self.y,
+            2 => &mut self.z,
+            _ => panic!("Point index out of range: {}", index),
+        }
+    }
+}
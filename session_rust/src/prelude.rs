@@ -0,0 +1,16 @@
+//! Convenience re-exports of the commonly used types.
+//!
+//! ```rust
+//! use session_rust::prelude::*;
+//!
+//! let mut point = Point::new(1.0, 2.0, 3.0);
+//! point.pointcolor = Color::new(255, 0, 0, 255);
+//! assert_eq!(point.pointcolor.r, 255);
+//!
+//! let format = JsonFormat::Compact;
+//! assert_eq!(format, JsonFormat::Compact);
+//! ```
+
+pub use crate::color::Color;
+pub use crate::json_format::JsonFormat;
+pub use crate::point::Point;
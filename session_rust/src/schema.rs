@@ -0,0 +1,51 @@
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+/// Version written to the `"version"` field of every serialized object.
+///
+/// Files written before versioning was introduced have no `"version"` field
+/// and are treated as version 0. Each type migrates older data to the current
+/// layout in its `from_json_data` before deserializing.
+pub const SCHEMA_VERSION: u64 = 1;
+
+/// Serializes an object followed by a `"version"` field.
+#[derive(Serialize)]
+pub(crate) struct Versioned<'a, T: Serialize> {
+    #[serde(flatten)]
+    object: &'a T,
+    version: u64,
+}
+
+impl<'a, T: Serialize> Versioned<'a, T> {
+    pub(crate) fn new(object: &'a T) -> Self {
+        Self {
+            object,
+            version: SCHEMA_VERSION,
+        }
+    }
+}
+
+/// Reads the schema version of `data`, defaulting to 0 when absent.
+///
+/// Returns an error for versions newer than [`SCHEMA_VERSION`].
+pub(crate) fn read_version(data: &Value) -> Result<u64, Box<dyn std::error::Error>> {
+    let version = match data.get("version") {
+        None => 0,
+        Some(value) => value
+            .as_u64()
+            .ok_or_else(|| format!("invalid version: {}", value))?,
+    };
+    if version > SCHEMA_VERSION {
+        return Err(format!(
+            "unsupported version {} (latest supported is {})",
+            version, SCHEMA_VERSION
+        )
+        .into());
+    }
+    Ok(version)
+}
+
+/// Inserts `value` under `key` if the object has no such key.
+pub(crate) fn insert_default(data: &mut Map<String, Value>, key: &str, value: Value) {
+    data.entry(key).or_insert(value);
+}